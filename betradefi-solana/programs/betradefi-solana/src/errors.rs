@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum BeTraDeFiError {
+    #[msg("Market must declare between 2 and MAX_OUTCOMES outcomes")]
+    InvalidOutcomeCount,
+    #[msg("Outcome index is out of range for this market")]
+    InvalidOutcome,
+    #[msg("Market is not open")]
+    MarketNotOpen,
+    #[msg("Market is not closed")]
+    MarketNotClosed,
+    #[msg("Market has not been resolved yet")]
+    MarketNotResolved,
+    #[msg("Market has already been resolved")]
+    MarketAlreadyResolved,
+    #[msg("This bet has already been claimed")]
+    AlreadyClaimed,
+    #[msg("Bet amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Fee must not exceed 10_000 basis points")]
+    InvalidFee,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct MarketCreated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub authority: Pubkey,
+    pub num_outcomes: u8,
+    pub close_ts: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BetPlaced {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketResolved {
+    pub market: Pubkey,
+    pub winning_outcome: u8,
+    pub total_pool: u64,
+    pub distributable: u64,
+    pub refund_mode: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinningsClaimed {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+}
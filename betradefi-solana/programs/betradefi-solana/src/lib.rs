@@ -1,4 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+mod errors;
+mod events;
+mod math;
+mod state;
+
+use errors::BeTraDeFiError;
+use events::*;
+use state::*;
 
 declare_id!("8P8E86Du9K1uo5CSeU1MkmRbEqDKvfWtwWdGijRZAuzf");
 
@@ -6,11 +17,461 @@ declare_id!("8P8E86Du9K1uo5CSeU1MkmRbEqDKvfWtwWdGijRZAuzf");
 pub mod betradefi_solana {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        msg!("Greetings from: {:?}", ctx.program_id);
+    /// Bootstraps the protocol by creating the single global `Config` PDA.
+    /// Must be called exactly once; calling it again fails because the
+    /// `init` constraint refuses to reinitialize an existing account.
+    pub fn initialize(ctx: Context<Initialize>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+        require!(fee_bps <= 10_000, BeTraDeFiError::InvalidFee);
+
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.treasury = treasury;
+        config.fee_bps = fee_bps;
+        config.paused = false;
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Creates a new market at the PDA `[b"market", market_id]`, owned by
+    /// the calling authority, open for betting until `close_ts`.
+    pub fn create_market(
+        ctx: Context<CreateMarket>,
+        market_id: u64,
+        close_ts: i64,
+        outcomes: u8,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, BeTraDeFiError::ProtocolPaused);
+        require!(
+            (2..=MAX_OUTCOMES as u8).contains(&outcomes),
+            BeTraDeFiError::InvalidOutcomeCount
+        );
+
+        let market = &mut ctx.accounts.market;
+        market.authority = ctx.accounts.authority.key();
+        market.mint = ctx.accounts.mint.key();
+        market.market_id = market_id;
+        market.created_at = Clock::get()?.unix_timestamp;
+        market.close_ts = close_ts;
+        market.status = MarketStatus::Open;
+        market.num_outcomes = outcomes;
+        market.stakes = [0; MAX_OUTCOMES];
+        market.bet_counts = [0; MAX_OUTCOMES];
+        market.winning_outcome = None;
+        market.fee_bps = 0;
+        market.distributable = 0;
+        market.refund_mode = false;
+        market.claims_remaining = 0;
+        market.bump = ctx.bumps.market;
+        market.vault_bump = ctx.bumps.vault;
+
+        emit!(MarketCreated {
+            market: market.key(),
+            market_id,
+            authority: market.authority,
+            num_outcomes: outcomes,
+            close_ts,
+            timestamp: market.created_at,
+        });
+        Ok(())
+    }
+
+    /// Stops new bets from being placed. Only the market's creator may
+    /// close it, and only while it is still open.
+    pub fn close_market(ctx: Context<ModifyMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Open, BeTraDeFiError::MarketNotOpen);
+        market.status = MarketStatus::Closed;
+        Ok(())
+    }
+
+    /// Settles the market on `winning_outcome`. Only callable by the
+    /// creator, and only once the market has been closed. Skims the
+    /// protocol fee and snapshots everything `claim_winnings` needs so a
+    /// later config or stake change can't affect an already-resolved
+    /// market. If nobody staked the winning outcome, falls back to
+    /// refunding every bettor's principal instead of dividing by zero.
+    pub fn resolve_market(ctx: Context<ResolveMarket>, winning_outcome: u8) -> Result<()> {
+        require!(!ctx.accounts.config.paused, BeTraDeFiError::ProtocolPaused);
+
+        let market = &mut ctx.accounts.market;
+        require!(
+            market.status != MarketStatus::Resolved,
+            BeTraDeFiError::MarketAlreadyResolved
+        );
+        require!(market.status == MarketStatus::Closed, BeTraDeFiError::MarketNotClosed);
+        require!(
+            winning_outcome < market.num_outcomes,
+            BeTraDeFiError::InvalidOutcome
+        );
+
+        let total_pool: u64 = market.stakes.iter().sum();
+        let winning_stake = market.stakes[winning_outcome as usize];
+        let settlement = math::resolve_settlement(
+            total_pool,
+            winning_stake,
+            ctx.accounts.config.fee_bps,
+            market.bet_counts[winning_outcome as usize],
+            market.bet_counts.iter().sum(),
+        );
+        let fee = settlement.fee;
+
+        market.refund_mode = settlement.refund_mode;
+        market.fee_bps = settlement.fee_bps;
+        market.distributable = settlement.distributable;
+        market.claims_remaining = settlement.claims_remaining;
+        market.status = MarketStatus::Resolved;
+        market.winning_outcome = Some(winning_outcome);
+
+        // Sweep the fee out of the vault now, so `distributable` matches
+        // what the vault actually holds for winners and the last claimant
+        // draining the vault's remaining balance doesn't also hand them
+        // the protocol's cut.
+        if fee > 0 {
+            let market_key = market.key();
+            let seeds: &[&[u8]] = &[b"vault", market_key.as_ref(), &[market.vault_bump]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.treasury_token_account.to_account_info(),
+                        authority: ctx.accounts.vault.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                fee,
+            )?;
+        }
+
+        emit!(MarketResolved {
+            market: market.key(),
+            winning_outcome,
+            total_pool,
+            distributable: market.distributable,
+            refund_mode: market.refund_mode,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
+
+    /// Escrows `amount` of the market's token into the market vault and
+    /// records (or tops up) the bettor's `Bet` receipt for `outcome`.
+    pub fn place_bet(ctx: Context<PlaceBet>, outcome: u8, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, BeTraDeFiError::ProtocolPaused);
+
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Open, BeTraDeFiError::MarketNotOpen);
+        require!(
+            Clock::get()?.unix_timestamp < market.close_ts,
+            BeTraDeFiError::MarketNotOpen
+        );
+        require!(outcome < market.num_outcomes, BeTraDeFiError::InvalidOutcome);
+        require!(amount > 0, BeTraDeFiError::ZeroAmount);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bettor_token_account.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.bettor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        market.stakes[outcome as usize] = market.stakes[outcome as usize]
+            .checked_add(amount)
+            .unwrap();
+
+        let bet = &mut ctx.accounts.bet;
+        let is_new_bet = bet.amount == 0;
+        bet.market = market.key();
+        bet.bettor = ctx.accounts.bettor.key();
+        bet.outcome = outcome;
+        bet.amount = bet.amount.checked_add(amount).unwrap();
+        bet.claimed = false;
+        bet.bump = ctx.bumps.bet;
+
+        if is_new_bet {
+            market.bet_counts[outcome as usize] += 1;
+        }
+
+        emit!(BetPlaced {
+            market: bet.market,
+            bettor: bet.bettor,
+            outcome,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Pays out a `Bet` from the market vault, signing the transfer with
+    /// the vault PDA's own seeds. Normally pays the pro-rata share of
+    /// `distributable`; in `refund_mode` every bettor reclaims their own
+    /// principal instead. Either way, whichever claim brings
+    /// `claims_remaining` to zero drains the vault outright, so rounding
+    /// dust from `math::winner_payout` never strands tokens behind.
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>, outcome: u8) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let bet = &mut ctx.accounts.bet;
+
+        require!(market.status == MarketStatus::Resolved, BeTraDeFiError::MarketNotResolved);
+        require!(!bet.claimed, BeTraDeFiError::AlreadyClaimed);
+        if !market.refund_mode {
+            let winning_outcome = market.winning_outcome.unwrap();
+            require!(outcome == winning_outcome, BeTraDeFiError::InvalidOutcome);
+        }
+
+        let winning_stake = market.stakes[market.winning_outcome.unwrap() as usize];
+        let (claims_remaining, payout) = math::claim_payout(
+            market.claims_remaining,
+            market.refund_mode,
+            bet.amount,
+            market.distributable,
+            winning_stake,
+            ctx.accounts.vault.amount,
+        )
+        .unwrap();
+        market.claims_remaining = claims_remaining;
+
+        let market_key = market.key();
+        let seeds: &[&[u8]] = &[b"vault", market_key.as_ref(), &[market.vault_bump]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.bettor_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                &[seeds],
+            ),
+            payout,
+        )?;
+
+        bet.claimed = true;
+
+        emit!(WinningsClaimed {
+            market: market_key,
+            bettor: bet.bettor,
+            outcome,
+            amount: payout,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Quotes the payout a bet of `amount` on `outcome` would receive if
+    /// the market resolved right now with no further bets placed, net of
+    /// the protocol fee `resolve_market` will skim. Callable by other
+    /// programs via CPI, which read the result off return data.
+    pub fn quote_payout(ctx: Context<QuotePayout>, outcome: u8, amount: u64) -> Result<u64> {
+        let market = &ctx.accounts.market;
+        require!(outcome < market.num_outcomes, BeTraDeFiError::InvalidOutcome);
+
+        let total_pool: u64 = market.stakes.iter().sum();
+        let projected_pool = total_pool.checked_add(amount).unwrap();
+        let projected_stake = market.stakes[outcome as usize].checked_add(amount).unwrap();
+
+        // A fresh market with no existing stake on `outcome` and a zero
+        // `amount` quote has nothing to divide by; there's also no bet to
+        // price, so the honest quote is zero rather than a division panic.
+        let payout = if projected_stake == 0 {
+            0
+        } else {
+            let distributable =
+                math::distributable_pool(projected_pool, ctx.accounts.config.fee_bps);
+            math::winner_payout(amount, distributable, projected_stake)
+        };
+
+        set_return_data(&payout.try_to_vec()?);
+        Ok(payout)
+    }
+
+    /// Reads the live pari-mutuel odds for `outcome` as a typed
+    /// `OddsResult`, surfaced through CPI return data.
+    pub fn current_odds(ctx: Context<ViewMarket>, outcome: u8) -> Result<OddsResult> {
+        let market = &ctx.accounts.market;
+        require!(outcome < market.num_outcomes, BeTraDeFiError::InvalidOutcome);
+
+        let total_pool: u64 = market.stakes.iter().sum();
+        let outcome_stake = market.stakes[outcome as usize];
+        let implied_probability_bps = if total_pool == 0 {
+            0
+        } else {
+            (outcome_stake as u128 * 10_000 / total_pool as u128) as u16
+        };
+
+        let odds = OddsResult {
+            outcome,
+            outcome_stake,
+            total_pool,
+            implied_probability_bps,
+        };
+
+        set_return_data(&odds.try_to_vec()?);
+        Ok(odds)
+    }
 }
 
 #[derive(Accounts)]
-pub struct Initialize {}
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Config::LEN,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CreateMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Market::LEN,
+        seeds = [b"market", market_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = vault,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ModifyMarket<'info> {
+    #[account(mut, has_one = authority)]
+    pub market: Account<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(mut, has_one = authority)]
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, address = config.treasury)]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct PlaceBet<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = Bet::LEN,
+        seeds = [b"bet", market.key().as_ref(), bettor.key().as_ref(), &[outcome]],
+        bump,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct ClaimWinnings<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"bet", market.key().as_ref(), bettor.key().as_ref(), &[outcome]],
+        bump = bet.bump,
+        has_one = market,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ViewMarket<'info> {
+    pub market: Account<'info, Market>,
+}
+
+#[derive(Accounts)]
+pub struct QuotePayout<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
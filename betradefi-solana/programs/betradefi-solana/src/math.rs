@@ -0,0 +1,228 @@
+//! Pari-mutuel settlement arithmetic shared by `resolve_market` and
+//! `claim_winnings`. Kept separate from the instruction handlers so the
+//! fee/rounding rules have one place to live and one place to audit.
+
+/// Protocol fee skimmed from the pool once, at resolution: `P * fee_bps / 10_000`.
+pub fn protocol_fee(pool: u64, fee_bps: u16) -> u64 {
+    ((pool as u128) * (fee_bps as u128) / 10_000) as u64
+}
+
+/// What's left of the pool to distribute to winners after the fee is taken.
+pub fn distributable_pool(pool: u64, fee_bps: u16) -> u64 {
+    pool - protocol_fee(pool, fee_bps)
+}
+
+/// A winner's pro-rata share of `distributable`: `stake * D / winning_stake`.
+/// Uses a u128 intermediate so `stake * distributable` can't overflow u64.
+/// Truncates toward zero, so the last claimant (see `claim_winnings`) must
+/// drain whatever dust this truncation leaves behind in the vault.
+pub fn winner_payout(stake: u64, distributable: u64, winning_stake: u64) -> u64 {
+    ((stake as u128) * (distributable as u128) / (winning_stake as u128)) as u64
+}
+
+/// What `resolve_market` works out once a winning outcome is picked: the
+/// fee it sweeps to the treasury, and everything it snapshots onto
+/// `Market` for `claim_winnings` to pay winners out afterward.
+pub struct Settlement {
+    pub refund_mode: bool,
+    pub fee_bps: u16,
+    pub fee: u64,
+    pub distributable: u64,
+    pub claims_remaining: u32,
+}
+
+/// Settles a market's pool: skims the protocol fee unless the winning
+/// outcome had zero stake, in which case there's nothing to divide and
+/// every bettor instead reclaims their own principal.
+pub fn resolve_settlement(
+    total_pool: u64,
+    winning_stake: u64,
+    config_fee_bps: u16,
+    bet_counts_winning: u32,
+    bet_counts_total: u32,
+) -> Settlement {
+    if winning_stake == 0 {
+        Settlement {
+            refund_mode: true,
+            fee_bps: 0,
+            fee: 0,
+            distributable: total_pool,
+            claims_remaining: bet_counts_total,
+        }
+    } else {
+        let fee = protocol_fee(total_pool, config_fee_bps);
+        Settlement {
+            refund_mode: false,
+            fee_bps: config_fee_bps,
+            fee,
+            distributable: total_pool - fee,
+            claims_remaining: bet_counts_winning,
+        }
+    }
+}
+
+/// What a single `claim_winnings` call pays out, and the `claims_remaining`
+/// count afterward. Whichever claim brings `claims_remaining` to zero
+/// drains whatever the vault still holds instead of using the pro-rata
+/// formula, so `winner_payout`'s truncation dust never gets stranded.
+/// Returns `None` if there was nothing left to claim (a bookkeeping bug
+/// upstream, not a state a well-formed market should ever reach).
+pub fn claim_payout(
+    claims_remaining_before: u32,
+    refund_mode: bool,
+    bet_amount: u64,
+    distributable: u64,
+    winning_stake: u64,
+    vault_balance: u64,
+) -> Option<(u32, u64)> {
+    let claims_remaining = claims_remaining_before.checked_sub(1)?;
+    let payout = if claims_remaining == 0 {
+        vault_balance
+    } else if refund_mode {
+        bet_amount
+    } else {
+        winner_payout(bet_amount, distributable, winning_stake)
+    };
+    Some((claims_remaining, payout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protocol_fee_skims_the_configured_bps() {
+        assert_eq!(protocol_fee(10_000, 250), 250);
+        assert_eq!(protocol_fee(1, 250), 0); // rounds down
+        assert_eq!(protocol_fee(1_000_000, 0), 0);
+    }
+
+    #[test]
+    fn distributable_pool_is_pool_minus_fee() {
+        assert_eq!(distributable_pool(10_000, 250), 9_750);
+        assert_eq!(distributable_pool(10_000, 0), 10_000);
+    }
+
+    #[test]
+    fn winner_payout_splits_pro_rata() {
+        // Two winners staked 300 and 700 out of a 1_000 winning pool,
+        // splitting a 900 distributable pool.
+        assert_eq!(winner_payout(300, 900, 1_000), 270);
+        assert_eq!(winner_payout(700, 900, 1_000), 630);
+    }
+
+    #[test]
+    fn winner_payout_truncates_leaving_dust_for_the_last_claimant() {
+        // 100 distributed across a winning stake of 3 with stakes of 1 each
+        // does not divide evenly; each individual payout truncates down.
+        let payout = winner_payout(1, 100, 3);
+        assert_eq!(payout, 33);
+        // The dust (100 - 33*3 == 1) is left in the vault for
+        // claim_winnings' last-claimant path to sweep up.
+    }
+
+    #[test]
+    fn winner_payout_handles_large_values_without_overflow() {
+        let stake = u64::MAX / 4;
+        let distributable = u64::MAX / 2;
+        let winning_stake = u64::MAX / 2;
+        assert_eq!(winner_payout(stake, distributable, winning_stake), stake);
+    }
+
+    /// Drives resolve_settlement and claim_payout through a two-bettor,
+    /// two-outcome market the way resolve_market/claim_winnings do,
+    /// standing in for an instruction-level test in lieu of a full
+    /// Anchor/bankrun harness.
+    #[test]
+    fn two_outcome_market_resolves_and_drains_the_vault() {
+        // Alice bets 100 on outcome 0 (the loser), Bob bets 50 on outcome 1
+        // (the winner and the only bettor there) - each has their own Bet
+        // PDA, so their bet_counts land on separate outcomes.
+        let stakes = [100u64, 50u64];
+        let bet_counts = [1u32, 1u32];
+        let total_pool: u64 = stakes.iter().sum();
+        let winning_outcome = 1usize;
+        let winning_stake = stakes[winning_outcome];
+        let fee_bps = 250u16; // 2.5%
+
+        let settlement = resolve_settlement(
+            total_pool,
+            winning_stake,
+            fee_bps,
+            bet_counts[winning_outcome],
+            bet_counts.iter().sum(),
+        );
+
+        assert!(!settlement.refund_mode);
+        assert_eq!(settlement.fee, protocol_fee(total_pool, fee_bps));
+        assert_eq!(settlement.distributable, total_pool - settlement.fee);
+        // Only Bob ever bet on the winning outcome.
+        assert_eq!(settlement.claims_remaining, 1);
+
+        // The vault held the full pool; resolve_market sweeps the fee to
+        // the treasury before any claim runs, so this is what's left.
+        let vault_after_fee_sweep = total_pool - settlement.fee;
+
+        let (claims_remaining, payout) = claim_payout(
+            settlement.claims_remaining,
+            settlement.refund_mode,
+            winning_stake, // Bob's bet.amount, the sole winning bettor
+            settlement.distributable,
+            winning_stake,
+            vault_after_fee_sweep,
+        )
+        .unwrap();
+
+        // Bob is the last (only) claimant, so he drains the vault outright
+        // rather than going through the pro-rata formula.
+        assert_eq!(claims_remaining, 0);
+        assert_eq!(payout, vault_after_fee_sweep);
+
+        // A second claim attempt has nothing left to decrement.
+        assert!(claim_payout(
+            claims_remaining,
+            settlement.refund_mode,
+            winning_stake,
+            settlement.distributable,
+            winning_stake,
+            0,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn zero_stake_winner_refunds_everyone_their_principal() {
+        let stakes = [100u64, 0u64];
+        let bet_counts = [1u32, 0u32];
+        let total_pool: u64 = stakes.iter().sum();
+        let winning_outcome = 1usize;
+
+        let settlement = resolve_settlement(
+            total_pool,
+            stakes[winning_outcome],
+            250,
+            bet_counts[winning_outcome],
+            bet_counts.iter().sum(),
+        );
+
+        assert!(settlement.refund_mode);
+        assert_eq!(settlement.fee, 0);
+        assert_eq!(settlement.distributable, total_pool);
+        // Nobody bet the (non-existent) winning outcome, so every bettor
+        // across every outcome gets to reclaim their principal.
+        assert_eq!(settlement.claims_remaining, 1);
+
+        let (claims_remaining, payout) = claim_payout(
+            settlement.claims_remaining,
+            settlement.refund_mode,
+            100, // the one bettor's principal, staked on the losing outcome
+            settlement.distributable,
+            stakes[winning_outcome],
+            total_pool,
+        )
+        .unwrap();
+
+        assert_eq!(claims_remaining, 0);
+        assert_eq!(payout, total_pool);
+    }
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+/// Maximum number of outcomes a single market may have. Bounds `Market`'s
+/// account size so it can be sized at `init` time.
+pub const MAX_OUTCOMES: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MarketStatus {
+    Open,
+    Closed,
+    Resolved,
+}
+
+/// A single betting/prediction market. Created via `create_market`,
+/// transitioned through `close_market` and `resolve_market` by the
+/// creating authority only.
+#[account]
+pub struct Market {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub market_id: u64,
+    pub created_at: i64,
+    pub close_ts: i64,
+    pub status: MarketStatus,
+    pub num_outcomes: u8,
+    pub stakes: [u64; MAX_OUTCOMES],
+    /// Number of distinct bettors on each outcome, incremented the first
+    /// time a bettor stakes on that outcome. Snapshotted into
+    /// `claims_remaining` at resolution so `claim_winnings` can tell when
+    /// it is paying out the last winner.
+    pub bet_counts: [u32; MAX_OUTCOMES],
+    pub winning_outcome: Option<u8>,
+    /// Fee rate snapshotted from `Config` at resolution, so a later fee
+    /// change can't affect an already-resolved market.
+    pub fee_bps: u16,
+    /// Pool remaining for winners after the protocol fee was skimmed.
+    pub distributable: u64,
+    /// True when the winning outcome had zero stake; in this case there
+    /// is nothing to divide, so every bettor reclaims their own principal
+    /// instead of a pro-rata share.
+    pub refund_mode: bool,
+    /// Unclaimed payouts left for this market. The claim that brings this
+    /// to zero drains whatever the vault still holds, so integer-division
+    /// dust never gets stranded.
+    pub claims_remaining: u32,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Market {
+    pub const LEN: usize = 8 // discriminator
+        + 32 // authority
+        + 32 // mint
+        + 8 // market_id
+        + 8 // created_at
+        + 8 // close_ts
+        + 1 // status
+        + 1 // num_outcomes
+        + 8 * MAX_OUTCOMES // stakes
+        + 4 * MAX_OUTCOMES // bet_counts
+        + (1 + 1) // winning_outcome
+        + 2 // fee_bps
+        + 8 // distributable
+        + 1 // refund_mode
+        + 4 // claims_remaining
+        + 1 // bump
+        + 1; // vault_bump
+}
+
+/// A bettor's receipt for a single wager. One `Bet` per (market, bettor,
+/// outcome), at the PDA `[b"bet", market, bettor, outcome]`, so a bettor
+/// backing two different outcomes on the same market gets two independent
+/// receipts instead of one conflated into the other's stake and count.
+#[account]
+pub struct Bet {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+impl Bet {
+    pub const LEN: usize = 8 // discriminator
+        + 32 // market
+        + 32 // bettor
+        + 1 // outcome
+        + 8 // amount
+        + 1 // claimed
+        + 1; // bump
+}
+
+/// Return type of `current_odds`, handed back through Anchor's CPI
+/// return-data mechanism so other programs can read live odds without
+/// deserializing `Market` themselves.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OddsResult {
+    pub outcome: u8,
+    pub outcome_stake: u64,
+    pub total_pool: u64,
+    pub implied_probability_bps: u16,
+}
+
+/// Singleton protocol config, created once by `initialize` at the
+/// deterministic PDA `[b"config"]`. Every later instruction that needs to
+/// gate on protocol-wide state (fees, pause switch, authority) reads this
+/// account instead of threading its own copy around.
+#[account]
+pub struct Config {
+    pub authority: Pubkey,
+    /// Token account the protocol fee is swept into at each market's
+    /// resolution. Must share the market's mint.
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl Config {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 1 + 1;
+}